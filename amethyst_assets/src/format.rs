@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use amethyst_error::Error;
+
+use crate::{loader::LoadContext, Asset, Source};
+
+/// The data produced by a `Format`.
+///
+/// Dependencies pulled in while producing `data` are *not* carried here --
+/// a format records them by calling `LoadContext::load`, which pushes onto
+/// the `LoadContext`'s own dependency list, read back afterwards with
+/// `LoadContext::into_dependencies`. `FormatValue` itself stays a plain
+/// wrapper around the data so formats with no dependencies (the common
+/// case) don't pay for bookkeeping they don't use.
+pub struct FormatValue<A: Asset> {
+    /// The asset data itself.
+    pub data: A::Data,
+}
+
+impl<A: Asset> FormatValue<A> {
+    /// Wraps bare asset data with no extra reload bookkeeping.
+    pub fn data(data: A::Data) -> Self {
+        FormatValue { data }
+    }
+}
+
+/// Turns the raw bytes of a `Source` into an asset's `Data`.
+pub trait Format<A: Asset>: Send + Sync + 'static {
+    /// A human-readable name for this format, used in errors and logs.
+    const NAME: &'static str;
+
+    /// Extra, format-specific configuration (e.g. mipmap levels for a
+    /// texture format).
+    type Options: Send + Sync;
+
+    /// Imports `name` from `source`, producing the asset's `Data`.
+    ///
+    /// `ctx` is handed a `LoadContext` so the format can load the other
+    /// assets it depends on through `LoadContext::load`, which also records
+    /// those dependencies so a later reload of one of them is propagated
+    /// back here. `create_reload` mirrors the loader's hot-reload setting,
+    /// in case a format wants to skip work (e.g. keeping source bytes
+    /// around) it only needs when reloading is possible.
+    fn import(
+        &self,
+        name: String,
+        source: Arc<dyn Source>,
+        ctx: &LoadContext,
+        options: Self::Options,
+        create_reload: bool,
+    ) -> Result<FormatValue<A>, Error>;
+}
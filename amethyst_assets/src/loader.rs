@@ -3,12 +3,15 @@ use std::{
     borrow::Borrow,
     hash::Hash,
     ops::Deref,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
+use crossbeam_channel::{Receiver, RecvTimeoutError};
 use fnv::FnvHashMap;
-use log::debug;
+use log::{debug, warn};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use rayon::ThreadPool;
 
 use amethyst_error::{Error as AmethystError, ResultExt};
@@ -17,10 +20,17 @@ use thread_profiler::profile_scope;
 
 use crate::{
     error::Error,
+    progress::Tracker,
     storage::{AssetStorage, Handle, Processed},
     Asset, Directory, Format, FormatValue, Progress, Source,
 };
 
+/// How long the hot-reload watcher waits with no further filesystem events
+/// before acting on the ones it has collected (the wait restarts on every
+/// new event), so a burst of writes to the same file (e.g. an editor doing a
+/// save-as-rename) only triggers one reload.
+const HOT_RELOAD_DEBOUNCE: Duration = Duration::from_millis(250);
+
 #[derive(Debug, Clone, Hash)]
 struct LoadInfo<'a> {
     path: &'a str,
@@ -41,12 +51,100 @@ impl<'a> From<LoadInfo<'a>> for LoadInfoHash {
     }
 }
 
+/// Splits a `scheme://rest` asset name into the source id named by `scheme`
+/// and the remaining path, falling back to the default source (`""`) when
+/// `name` carries no scheme.
+fn split_source_prefix(name: &str) -> (&str, &str) {
+    match name.find("://") {
+        Some(pos) => (&name[..pos], &name[pos + 3..]),
+        None => ("", name),
+    }
+}
+
+/// Looks up a source by id in `sources`, surfacing a clear error instead of
+/// panicking when `source` names a source that was never registered with
+/// `Loader::add_source`.
+fn resolve_source(
+    sources: &FnvHashMap<String, Arc<dyn Source>>,
+    source: &str,
+) -> Result<Arc<dyn Source>, AmethystError> {
+    sources.get(source).cloned().ok_or_else(|| {
+        AmethystError::from_string(format!(
+            "No such source: {:?}. Maybe you forgot to add it with `Loader::add_source`, or \
+             used an unknown scheme in the asset name?",
+            source
+        ))
+    })
+}
+
+/// Registers `progress` as tracking one more asset and boxes up the tracker
+/// it hands back, so the rest of the loading pipeline can deal in a single
+/// object-safe `Box<dyn Tracker>` instead of being generic over `Progress`.
+fn start_tracking<P: Progress>(mut progress: P) -> Box<dyn Tracker> {
+    progress.add_assets(1);
+    Box::new(progress.create_tracker())
+}
+
+/// A cached handle, together with everything needed to service a hot-reload
+/// of the asset it belongs to: the source/path it was loaded from (so a
+/// filesystem change can be matched back to it), the source/path pairs its
+/// format pulled in while importing it (so reloading *those* can cascade
+/// here too, even when a dependency comes from a different source), and a
+/// closure that reruns the whole import from scratch.
+struct HandleEntry {
+    handle: Box<dyn Any + Send + Sync>,
+    source: String,
+    path: String,
+    dependencies: Vec<(String, String)>,
+    reimport: Arc<dyn Fn() + Send + Sync>,
+}
+
+/// A single coalesced filesystem change, translated from whatever mix of
+/// create/modify/remove events `notify` reported for a path into the one
+/// thing the loader cares about: that `path` in `source_id` needs
+/// reimporting.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Reload {
+    Changed { source_id: String, path: String },
+}
+
+/// Picks between the two ways `Loader::load_with_mode` can load an asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadMode {
+    /// Import on a worker thread and hand back a `Handle<A>` immediately;
+    /// the usual way to load assets.
+    Deferred,
+    /// Import on the calling thread and block for the data; see
+    /// `Loader::load_immediate`.
+    Immediate,
+}
+
+/// The result of `Loader::load_with_mode`: a deferred handle, or the
+/// already-imported data (or import error) from an immediate load.
+pub enum Loaded<A: Asset> {
+    Deferred(Handle<A>),
+    Immediate(Result<A::Data, AmethystError>),
+}
+
 /// The asset loader, holding the sources and a reference to the `ThreadPool`.
 pub struct Loader {
     hot_reload: bool,
     pool: Arc<ThreadPool>,
-    sources: FnvHashMap<String, Arc<dyn Source>>,
-    handles: Mutex<FnvHashMap<LoadInfoHash, Box<dyn Any + Send + Sync>>>,
+    sources: Arc<FnvHashMap<String, Arc<dyn Source>>>,
+    handles: Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>>,
+    /// One live hot-reload watcher per source that has one, keyed by source
+    /// id. Only ever touched from the thread that owns the `Loader` (every
+    /// method that reaches it takes `&mut self`), so, like `formats`, it
+    /// doesn't need the `Arc<Mutex<_>>` treatment `handles` gets. Dropping
+    /// an entry (or the whole map, in `set_hot_reload(false)`) tears down
+    /// that watcher's thread; see `WatcherGuard`.
+    watchers: FnvHashMap<String, WatcherGuard>,
+    /// Default `Format<A>` per asset type, registered with `register_format`
+    /// and consulted by `load_typed` so call sites don't have to name a
+    /// concrete format. Only ever touched from the thread that owns the
+    /// `Loader`, so it doesn't need the `Arc<Mutex<_>>` treatment `handles`
+    /// gets.
+    formats: FnvHashMap<TypeId, Box<dyn Any + Send + Sync>>,
 }
 
 impl Loader {
@@ -65,10 +163,15 @@ impl Loader {
         S: Source,
     {
         let mut loader = Loader {
-            hot_reload: true,
+            // Off by default: hot-reload watchers spawn real OS resources
+            // (a thread, a filesystem watch) that nothing should pay for
+            // until it's explicitly asked for with `set_hot_reload(true)`.
+            hot_reload: false,
             pool,
             sources: Default::default(),
             handles: Default::default(),
+            watchers: Default::default(),
+            formats: Default::default(),
         };
 
         loader.set_default_source(source);
@@ -76,13 +179,28 @@ impl Loader {
     }
 
     /// Add a source to the `Loader`, given an id and the source.
+    ///
+    /// If hot-reloading is enabled (see `set_hot_reload`) and the source
+    /// exposes a root path (i.e. it's backed by the filesystem), this spawns
+    /// a background `notify` watcher over it so assets loaded from it are
+    /// automatically reimported on change. Sources that can't be watched
+    /// (in-memory, network, ...) just skip that step.
     pub fn add_source<I, S>(&mut self, id: I, source: S)
     where
         I: Into<String>,
         S: Source,
     {
-        self.sources
-            .insert(id.into(), Arc::new(source) as Arc<dyn Source>);
+        let id = id.into();
+        let source = Arc::new(source) as Arc<dyn Source>;
+
+        if self.hot_reload {
+            // A source replacing one already registered under `id` gets a
+            // watcher over its own root, not the old source's.
+            self.watchers.remove(&id);
+            self.spawn_watcher_for(id.clone(), &source);
+        }
+
+        Arc::make_mut(&mut self.sources).insert(id, source);
     }
 
     /// Set the default source of the `Loader`.
@@ -93,17 +211,46 @@ impl Loader {
         self.add_source(String::new(), source);
     }
 
-    /// If set to `true`, this `Loader` will ask formats to
-    /// generate "reload instructions" which *allow* reloading.
-    /// Calling `set_hot_reload(true)` does not actually enable
-    /// hot reloading; this is controlled by the `HotReloadStrategy`
-    /// resource.
+    /// Enables or disables hot-reloading.
+    ///
+    /// Turning it on spawns a filesystem watcher (see `add_source`) for
+    /// every currently-registered source that has a root path, including
+    /// ones added before this call, and any source added afterwards gets
+    /// one immediately. Turning it off tears down every live watcher --
+    /// their threads are stopped and joined, not just ignored -- so no
+    /// further reimports fire until hot-reloading is turned back on.
     pub fn set_hot_reload(&mut self, value: bool) {
+        if value == self.hot_reload {
+            return;
+        }
         self.hot_reload = value;
+
+        if value {
+            let sources = self.sources.clone();
+            for (id, source) in sources.iter() {
+                self.spawn_watcher_for(id.clone(), source);
+            }
+        } else {
+            self.watchers.clear();
+        }
+    }
+
+    /// Spawns a watcher for `source` under `id` unless one is already
+    /// running for it.
+    fn spawn_watcher_for(&mut self, id: String, source: &Arc<dyn Source>) {
+        if self.watchers.contains_key(&id) {
+            return;
+        }
+
+        if let Some(guard) = spawn_watcher(id.clone(), source, self.handles.clone()) {
+            self.watchers.insert(id, guard);
+        }
     }
 
-    /// Loads an asset with a given format from the default (directory) source.
-    /// If you want to load from a custom source instead, use `load_from`.
+    /// Loads an asset with a given format from the source named by an
+    /// optional `scheme://` prefix on `name`, defaulting to the directory
+    /// source when no scheme is present. If you want to pick a source without
+    /// embedding it in `name`, use `load_from`.
     ///
     /// See `load_from` for more information.
     pub fn load<A, F, N, P>(
@@ -116,17 +263,22 @@ impl Loader {
     ) -> Handle<A>
     where
         A: Asset,
-        F: Format<A>,
+        F: Format<A> + Clone,
+        F::Options: Clone,
         N: Into<String>,
         P: Progress,
     {
         #[cfg(feature = "profiler")]
         profile_scope!("initialise_loading_assets");
-        self.load_from::<A, F, _, _, _>(name, format, options, "", progress, storage)
+        let name = name.into();
+        let (source, name) = split_source_prefix(&name);
+        self.load_from::<A, F, _, _, _>(name, format, options, source, progress, storage)
     }
 
-    /// Loads an asset with a given format from the default (directory) source.
-    /// If you want to load from a custom source instead, use `load_from`.
+    /// Loads an asset with a given format from the source named by an
+    /// optional `scheme://` prefix on `name`, defaulting to the directory
+    /// source when no scheme is present. If you want to pick a source without
+    /// embedding it in `name`, use `load_from`.
     ///
     /// See `load_from` for more information.
     pub fn load_or_else<A, F, N, P, EPtr>(
@@ -140,7 +292,8 @@ impl Loader {
     ) -> Handle<A>
     where
         A: Asset,
-        F: Format<A>,
+        F: Format<A> + Clone,
+        F::Options: Clone,
         N: Into<String>,
         P: Progress,
         EPtr: Deref + Send + 'static,
@@ -148,8 +301,10 @@ impl Loader {
     {
         #[cfg(feature = "profiler")]
         profile_scope!("initialise_loading_assets");
+        let name = name.into();
+        let (source, name) = split_source_prefix(&name);
         self.load_from_or_else::<A, F, _, _, _, _>(
-            name, format, options, "", progress, storage, or_else,
+            name, format, options, source, progress, storage, or_else,
         )
     }
 
@@ -164,7 +319,8 @@ impl Loader {
     ) -> Handle<A>
     where
         A: Asset,
-        F: Format<A> + 'static,
+        F: Format<A> + Clone + 'static,
+        F::Options: Clone,
         N: Into<String>,
         P: Progress,
         S: AsRef<str> + Eq + Hash + ?Sized,
@@ -196,13 +352,14 @@ impl Loader {
         format: F,
         options: F::Options,
         source: &S,
-        mut progress: P,
+        progress: P,
         storage: &AssetStorage<A>,
         or_else: EPtr,
     ) -> Handle<A>
     where
         A: Asset,
-        F: Format<A> + 'static,
+        F: Format<A> + Clone + 'static,
+        F::Options: Clone,
         N: Into<String>,
         P: Progress,
         S: AsRef<str> + Eq + Hash + ?Sized,
@@ -212,80 +369,19 @@ impl Loader {
     {
         #[cfg(feature = "profiler")]
         profile_scope!("load_asset_from");
-        use crate::progress::Tracker;
-
-        let name = name.into();
-        let source = source.as_ref();
-        let format_name = F::NAME;
-        let source_name = match source {
-            "" => "[default source]",
-            other => other,
-        };
-
-        let key: LoadInfoHash = LoadInfo {
-            path: &name,
-            source,
-            type_id: TypeId::of::<A>(),
-        }
-        .into();
-
-        let handle = {
-            let mut handles = self
-                .handles
-                .lock()
-                .expect("Programmer error: Thread panicked while holding handles lock");
-
-            if let Some(handle) = handles.get(&key) {
-                return handle
-                    .downcast_ref::<Handle<A>>()
-                    .expect("Programmer error: Incorrect type added to map!")
-                    .clone();
-            }
-
-            let handle = storage.allocate();
-
-            handles.insert(key, Box::new(handle.clone()));
-
-            handle
-        };
-
-        debug!(
-            "{:?}: Loading asset {:?} with format {:?} from source {:?} (handle id: {:?})",
-            A::NAME,
+        load_asset(
+            &self.sources,
+            &self.pool,
+            &self.handles,
+            self.hot_reload,
             name,
-            format_name,
-            source_name,
-            handle,
-        );
-
-        progress.add_assets(1);
-        let tracker = progress.create_tracker();
-
-        let source = self.source(source);
-        let handle_clone = handle.clone();
-        let processed = storage.processed.clone();
-
-        let hot_reload = self.hot_reload;
-
-        let cl = move || {
-            #[cfg(feature = "profiler")]
-            profile_scope!("load_asset_from_worker");
-            let data = format
-                .import(name.clone(), source, options, hot_reload)
-                .or_else(|err| or_else(err).map(FormatValue::data))
-                .with_context(|_| Error::Format(F::NAME));
-            let tracker = Box::new(tracker) as Box<dyn Tracker>;
-
-            processed.push(Processed::NewAsset {
-                data,
-                handle,
-                name,
-                tracker,
-            });
-        };
-        self.pool.spawn(cl);
-
-        handle_clone
+            format,
+            options,
+            source,
+            start_tracking(progress),
+            storage,
+            or_else,
+        )
     }
 
     /// Load an asset from data and return a handle.
@@ -313,10 +409,799 @@ impl Loader {
         handle
     }
 
-    fn source(&self, source: &str) -> Arc<dyn Source> {
-        self.sources
-            .get(source)
-            .expect("No such source. Maybe you forgot to add it with `Loader::add_source`?")
-            .clone()
+    /// Synchronously imports `name` with `format` on the calling thread and
+    /// returns the data directly, bypassing both the handle cache and the
+    /// `processed` queue. This blocks until the import finishes, so it's
+    /// meant for bootstrapping and for formats that need a dependency's data
+    /// *right now* (e.g. reading an index file to know what else to load)
+    /// rather than waiting on the async pipeline -- not for everyday asset
+    /// loading, which should go through `load`/`load_from`.
+    pub fn load_immediate<A, F, N, S>(
+        &self,
+        name: N,
+        format: F,
+        options: F::Options,
+        source: &S,
+    ) -> Result<A::Data, AmethystError>
+    where
+        A: Asset,
+        F: Format<A> + Clone + 'static,
+        N: Into<String>,
+        S: AsRef<str> + Eq + Hash + ?Sized,
+        String: Borrow<S>,
+    {
+        let name = name.into();
+        let resolved_source = resolve_source(&self.sources, source.as_ref())?;
+        let ctx = LoadContext::new(
+            self.sources.clone(),
+            self.pool.clone(),
+            self.handles.clone(),
+            self.hot_reload,
+        );
+
+        format
+            .import(name, resolved_source, &ctx, options, self.hot_reload)
+            .map(|value| value.data)
+            .with_context(|_| Error::Format(F::NAME))
+    }
+
+    /// Loads `name` the way `mode` says to: `LoadMode::Deferred` behaves
+    /// like `load_from` and hands back a `Handle<A>` that resolves once a
+    /// worker thread finishes the import, while `LoadMode::Immediate`
+    /// behaves like `load_immediate` and blocks the calling thread for the
+    /// owned `A::Data`. Lets a single call site pick its loading strategy
+    /// without duplicating the `name`/`format`/`options`/`source` wiring.
+    pub fn load_with_mode<A, F, N, P, S>(
+        &self,
+        mode: LoadMode,
+        name: N,
+        format: F,
+        options: F::Options,
+        source: &S,
+        progress: P,
+        storage: &AssetStorage<A>,
+    ) -> Loaded<A>
+    where
+        A: Asset,
+        F: Format<A> + Clone + 'static,
+        F::Options: Clone,
+        N: Into<String>,
+        P: Progress,
+        S: AsRef<str> + Eq + Hash + ?Sized,
+        String: Borrow<S>,
+    {
+        match mode {
+            LoadMode::Deferred => Loaded::Deferred(
+                self.load_from(name, format, options, source, progress, storage),
+            ),
+            LoadMode::Immediate => {
+                Loaded::Immediate(self.load_immediate(name, format, options, source))
+            }
+        }
+    }
+
+    /// Registers `format` as the default `Format<A>` used by `load_typed`,
+    /// so call sites that only know the asset type (e.g. a path with no or
+    /// the wrong extension) can still load it.
+    pub fn register_format<A, F>(&mut self, format: F)
+    where
+        A: Asset,
+        F: Format<A> + Clone + 'static,
+        F::Options: Clone + 'static,
+    {
+        let erased: Box<dyn ErasedFormat<A>> = Box::new(TypedFormat(format));
+        self.formats
+            .insert(TypeId::of::<A>(), Box::new(erased) as Box<dyn Any + Send + Sync>);
+    }
+
+    /// Loads an asset of type `A` using the `Format<A>` registered for it
+    /// with `register_format`, instead of requiring the call site to name a
+    /// concrete format. Because `type_id` already participates in the
+    /// `LoadInfo` dedup key, the same path can be loaded as more than one
+    /// asset type this way without the handles colliding.
+    pub fn load_typed<A, N, P>(
+        &self,
+        name: N,
+        options: Box<dyn Any + Send + Sync>,
+        progress: P,
+        storage: &AssetStorage<A>,
+    ) -> Handle<A>
+    where
+        A: Asset,
+        N: Into<String>,
+        P: Progress,
+    {
+        let name = name.into();
+        let (source, name) = split_source_prefix(&name);
+        let tracker = start_tracking(progress);
+
+        match self
+            .formats
+            .get(&TypeId::of::<A>())
+            .and_then(|erased| erased.downcast_ref::<Box<dyn ErasedFormat<A>>>())
+        {
+            Some(format) => format.load_erased(
+                &self.sources,
+                &self.pool,
+                &self.handles,
+                self.hot_reload,
+                name,
+                source,
+                options,
+                tracker,
+                storage,
+            ),
+            None => {
+                let handle = storage.allocate();
+                storage.processed.push(Processed::NewAsset {
+                    data: Err(AmethystError::from_string(format!(
+                        "No format registered for {:?}. Did you forget to call \
+                         `Loader::register_format`?",
+                        A::NAME
+                    ))),
+                    handle: handle.clone(),
+                    name: name.to_string(),
+                    tracker,
+                });
+                handle
+            }
+        }
+    }
+}
+
+/// Object-safe wrapper around a registered `Format<A>`, letting
+/// `Loader::load_typed` invoke it without knowing the concrete format type;
+/// `Loader::register_format` is what fixes the concrete `F` the one time it
+/// needs to be known, at registration.
+trait ErasedFormat<A: Asset>: Send + Sync {
+    #[allow(clippy::too_many_arguments)]
+    fn load_erased(
+        &self,
+        sources: &Arc<FnvHashMap<String, Arc<dyn Source>>>,
+        pool: &Arc<ThreadPool>,
+        handles: &Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>>,
+        hot_reload: bool,
+        name: &str,
+        source: &str,
+        options: Box<dyn Any + Send + Sync>,
+        tracker: Box<dyn Tracker>,
+        storage: &AssetStorage<A>,
+    ) -> Handle<A>;
+}
+
+struct TypedFormat<F>(F);
+
+impl<A, F> ErasedFormat<A> for TypedFormat<F>
+where
+    A: Asset,
+    F: Format<A> + Clone + 'static,
+    F::Options: Clone + 'static,
+{
+    fn load_erased(
+        &self,
+        sources: &Arc<FnvHashMap<String, Arc<dyn Source>>>,
+        pool: &Arc<ThreadPool>,
+        handles: &Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>>,
+        hot_reload: bool,
+        name: &str,
+        source: &str,
+        options: Box<dyn Any + Send + Sync>,
+        tracker: Box<dyn Tracker>,
+        storage: &AssetStorage<A>,
+    ) -> Handle<A> {
+        let options = *options.downcast::<F::Options>().unwrap_or_else(|_| {
+            panic!(
+                "Programmer error: options passed to `Loader::load_typed` for {:?} don't match \
+                 the `Options` type of the format registered with `register_format`",
+                F::NAME
+            )
+        });
+
+        load_asset(
+            sources,
+            pool,
+            handles,
+            hot_reload,
+            name,
+            self.0.clone(),
+            options,
+            source,
+            tracker,
+            storage,
+            &|err| Err(err),
+        )
+    }
+}
+
+/// Handed to a `Format` while it imports an asset, so the format can load the
+/// other assets it depends on (e.g. the textures a material references)
+/// instead of reaching back into global state. Cheaply cloned from the
+/// `Loader`'s `sources`/`pool`/`handles` rather than borrowing the `Loader`
+/// itself, since the import runs on a worker thread.
+pub struct LoadContext {
+    sources: Arc<FnvHashMap<String, Arc<dyn Source>>>,
+    pool: Arc<ThreadPool>,
+    handles: Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>>,
+    hot_reload: bool,
+    dependencies: Mutex<Vec<(String, String)>>,
+}
+
+impl LoadContext {
+    fn new(
+        sources: Arc<FnvHashMap<String, Arc<dyn Source>>>,
+        pool: Arc<ThreadPool>,
+        handles: Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>>,
+        hot_reload: bool,
+    ) -> Self {
+        LoadContext {
+            sources,
+            pool,
+            handles,
+            hot_reload,
+            dependencies: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Loads a dependency of the asset currently being imported, recording
+    /// its source and path so a later reload of the dependency -- even one
+    /// loaded from a different source than the asset importing it -- can be
+    /// propagated to the asset being imported right now.
+    pub fn load<A, F, N, P>(
+        &self,
+        name: N,
+        format: F,
+        options: F::Options,
+        progress: P,
+        storage: &AssetStorage<A>,
+    ) -> Handle<A>
+    where
+        A: Asset,
+        F: Format<A> + Clone + 'static,
+        F::Options: Clone,
+        N: Into<String>,
+        P: Progress,
+    {
+        let name = name.into();
+        let (source, name) = split_source_prefix(&name);
+        self.dependencies
+            .lock()
+            .expect("Programmer error: Thread panicked while holding dependency lock")
+            .push((source.to_string(), name.to_string()));
+        load_asset(
+            &self.sources,
+            &self.pool,
+            &self.handles,
+            self.hot_reload,
+            name,
+            format,
+            options,
+            source,
+            start_tracking(progress),
+            storage,
+            &|err| Err(err),
+        )
+    }
+
+    fn into_dependencies(self) -> Vec<(String, String)> {
+        self.dependencies
+            .into_inner()
+            .expect("Programmer error: Thread panicked while holding dependency lock")
+    }
+}
+
+/// Shared implementation behind `Loader::load_from_or_else` and
+/// `LoadContext::load`: both just differ in which `sources`/`pool`/`handles`
+/// they operate on, and whether the import runs the top-level `or_else`.
+#[allow(clippy::too_many_arguments)]
+fn load_asset<A, F, N, S, EPtr>(
+    sources: &Arc<FnvHashMap<String, Arc<dyn Source>>>,
+    pool: &Arc<ThreadPool>,
+    handles: &Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>>,
+    hot_reload: bool,
+    name: N,
+    format: F,
+    options: F::Options,
+    source: &S,
+    tracker: Box<dyn Tracker>,
+    storage: &AssetStorage<A>,
+    or_else: EPtr,
+) -> Handle<A>
+where
+    A: Asset,
+    F: Format<A> + Clone + 'static,
+    F::Options: Clone,
+    N: Into<String>,
+    S: AsRef<str> + Eq + Hash + ?Sized,
+    String: Borrow<S>,
+    EPtr: Deref + Send + 'static,
+    EPtr::Target: Fn(AmethystError) -> Result<A::Data, AmethystError>,
+{
+    let name = name.into();
+    let source = source.as_ref();
+    let format_name = F::NAME;
+    let source_name = match source {
+        "" => "[default source]",
+        other => other,
+    };
+
+    let key: LoadInfoHash = LoadInfo {
+        path: &name,
+        source,
+        type_id: TypeId::of::<A>(),
+    }
+    .into();
+
+    // Resolved before anything is inserted into the cache: if `source` names a source
+    // that isn't registered, we want a fresh attempt (and a fresh error)
+    // every time `name` is loaded, not a poisoned cache entry that a later
+    // `Loader::add_source` can't unstick.
+    let (handle, resolved_source) = {
+        let mut handles = handles
+            .lock()
+            .expect("Programmer error: Thread panicked while holding handles lock");
+
+        if let Some(entry) = handles.get(&key) {
+            return entry
+                .handle
+                .downcast_ref::<Handle<A>>()
+                .expect("Programmer error: Incorrect type added to map!")
+                .clone();
+        }
+
+        match resolve_source(sources, source) {
+            Ok(resolved) => {
+                let handle = storage.allocate();
+
+                handles.insert(
+                    key.clone(),
+                    HandleEntry {
+                        handle: Box::new(handle.clone()),
+                        source: source.to_string(),
+                        path: name.clone(),
+                        dependencies: Vec::new(),
+                        reimport: Arc::new(|| {}),
+                    },
+                );
+
+                (handle, resolved)
+            }
+            Err(err) => {
+                let handle = storage.allocate();
+                storage.processed.push(Processed::NewAsset {
+                    data: Err(err),
+                    handle: handle.clone(),
+                    name,
+                    tracker,
+                });
+                return handle;
+            }
+        }
+    };
+
+    debug!(
+        "{:?}: Loading asset {:?} with format {:?} from source {:?} (handle id: {:?})",
+        A::NAME,
+        name,
+        format_name,
+        source_name,
+        handle,
+    );
+
+    let handle_clone = handle.clone();
+    let processed = storage.processed.clone();
+
+    // Build the closure that reruns this exact import from scratch, so the
+    // hot-reload watcher (or a parent asset whose own dependency list
+    // includes this path) can ask for a reimport without going through
+    // `Loader` again.
+    let reimport: Arc<dyn Fn() + Send + Sync> = {
+        let sources = sources.clone();
+        let pool = pool.clone();
+        let handles = handles.clone();
+        let format = format.clone();
+        let options = options.clone();
+        let name = name.clone();
+        let resolved_source = resolved_source.clone();
+        let processed = processed.clone();
+        let key = key.clone();
+        // Its own clone of the handle, so the `handle_clone` returned to the
+        // caller below isn't moved into this closure.
+        let reimport_handle = handle_clone.clone();
+
+        Arc::new(move || {
+            let ctx = LoadContext::new(sources.clone(), pool.clone(), handles.clone(), hot_reload);
+            let format = format.clone();
+            let name = name.clone();
+            let resolved_source = resolved_source.clone();
+            let options = options.clone();
+            let handle = reimport_handle.clone();
+            let processed = processed.clone();
+            let handles = handles.clone();
+            let key = key.clone();
+
+            pool.spawn(move || {
+                #[cfg(feature = "profiler")]
+                profile_scope!("reimport_asset_worker");
+                let data = format
+                    .import(name.clone(), resolved_source, &ctx, options, hot_reload)
+                    .with_context(|_| Error::Format(F::NAME));
+
+                if let Some(entry) = handles
+                    .lock()
+                    .expect("Programmer error: Thread panicked while holding handles lock")
+                    .get_mut(&key)
+                {
+                    entry.dependencies = ctx.into_dependencies();
+                }
+
+                // Reloads have no caller waiting on progress, so report
+                // through the no-op tracker.
+                let mut progress = ();
+                progress.add_assets(1);
+                let tracker = Box::new(progress.create_tracker()) as Box<dyn Tracker>;
+
+                processed.push(Processed::NewAsset {
+                    data,
+                    handle,
+                    name,
+                    tracker,
+                });
+            });
+        })
+    };
+
+    if let Some(entry) = handles
+        .lock()
+        .expect("Programmer error: Thread panicked while holding handles lock")
+        .get_mut(&key)
+    {
+        entry.reimport = reimport;
+    }
+
+    let ctx_sources = sources.clone();
+    let ctx_pool = pool.clone();
+    let ctx_handles = handles.clone();
+    let update_handles = handles.clone();
+
+    let cl = move || {
+        #[cfg(feature = "profiler")]
+        profile_scope!("load_asset_from_worker");
+        let ctx = LoadContext::new(ctx_sources, ctx_pool, ctx_handles, hot_reload);
+        let data = format
+            .import(name.clone(), resolved_source, &ctx, options, hot_reload)
+            .or_else(|err| or_else(err).map(FormatValue::data))
+            .with_context(|_| Error::Format(F::NAME));
+
+        if let Some(entry) = update_handles
+            .lock()
+            .expect("Programmer error: Thread panicked while holding handles lock")
+            .get_mut(&key)
+        {
+            entry.dependencies = ctx.into_dependencies();
+        }
+
+        processed.push(Processed::NewAsset {
+            data,
+            handle,
+            name,
+            tracker,
+        });
+    };
+    pool.spawn(cl);
+
+    handle_clone
+}
+
+/// A live hot-reload watcher: the `notify` watcher itself plus the thread
+/// blocked reading its events. Dropping it tears the watcher down -- which
+/// disconnects the channel the thread is blocked on -- and then joins the
+/// thread, so a `Loader` never leaks a watcher thread, its `notify` handle,
+/// or the `Arc<Mutex<_>>` clone of `handles` that thread was holding.
+struct WatcherGuard {
+    // `Option` so `Drop` can `take()` the watcher out and drop it *before*
+    // joining the thread below: dropping the watcher is what makes the
+    // thread's blocking `rx.recv()` return, which is what lets the join
+    // finish instead of hanging forever.
+    watcher: Option<RecommendedWatcher>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Drop for WatcherGuard {
+    fn drop(&mut self) {
+        self.watcher.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Spawns a background thread that watches `source`'s root directory (if it
+/// has one) for changes and reimports whichever cached handles match, using
+/// a `notify` watcher bridged onto a crossbeam channel. Sources with no
+/// filesystem root (in-memory, network, ...) are left unwatched, returning
+/// `None`.
+fn spawn_watcher(
+    source_id: String,
+    source: &Arc<dyn Source>,
+    handles: Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>>,
+) -> Option<WatcherGuard> {
+    let root = source.path()?;
+    // `notify` reports absolute, canonical paths on most platforms; a
+    // `Directory` rooted at a relative or symlinked path would otherwise
+    // never match in `collect_reloads`'s `strip_prefix`. Canonicalizing
+    // here (once, up front) instead of per-event keeps that check cheap.
+    let root = root.canonicalize().unwrap_or(root);
+
+    let (tx, rx) = crossbeam_channel::unbounded();
+    let mut watcher: RecommendedWatcher = match notify::recommended_watcher(move |event| {
+        let _ = tx.send(event);
+    }) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            warn!(
+                "Failed to create hot-reload watcher for source {:?}: {}",
+                source_id, err
+            );
+            return None;
+        }
+    };
+
+    if let Err(err) = watcher.watch(&root, RecursiveMode::Recursive) {
+        warn!(
+            "Failed to watch {:?} for source {:?}: {}",
+            root, source_id, err
+        );
+        return None;
+    }
+
+    let thread_source_id = source_id.clone();
+    let result = std::thread::Builder::new()
+        .name(format!("amethyst-hot-reload-{}", source_id))
+        .spawn(move || {
+            watch_loop(&thread_source_id, &root, &rx, &handles);
+        });
+
+    match result {
+        Ok(thread) => Some(WatcherGuard {
+            watcher: Some(watcher),
+            thread: Some(thread),
+        }),
+        Err(err) => {
+            warn!("Failed to spawn hot-reload watcher thread: {}", err);
+            // `watcher` is dropped here, which stops the watch; there's no
+            // thread to join.
+            None
+        }
+    }
+}
+
+/// Blocks on `rx` for filesystem events, debounces bursts of them, and hands
+/// the coalesced set of changed paths to `reload_asset`.
+fn watch_loop(
+    source_id: &str,
+    root: &Path,
+    rx: &Receiver<notify::Result<notify::Event>>,
+    handles: &Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>>,
+) {
+    let mut pending: FnvHashMap<Reload, ()> = Default::default();
+
+    while let Ok(event) = rx.recv() {
+        collect_reloads(source_id, root, event, &mut pending);
+
+        loop {
+            match rx.recv_timeout(HOT_RELOAD_DEBOUNCE) {
+                Ok(event) => collect_reloads(source_id, root, event, &mut pending),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        for reload in pending.drain().map(|(reload, ())| reload) {
+            reload_asset(reload, handles);
+        }
+    }
+}
+
+/// Translates one raw `notify` event into `Reload`s relative to `root`,
+/// coalescing into `pending` so duplicate paths within the debounce window
+/// only trigger a single reload.
+fn collect_reloads(
+    source_id: &str,
+    root: &Path,
+    event: notify::Result<notify::Event>,
+    pending: &mut FnvHashMap<Reload, ()>,
+) {
+    let event = match event {
+        Ok(event) => event,
+        Err(err) => {
+            warn!("Hot-reload watcher for source {:?} failed: {}", source_id, err);
+            return;
+        }
+    };
+
+    for path in event.paths {
+        // `root` is canonical (see `spawn_watcher`); canonicalize the event
+        // path the same way so a relative/symlinked root still matches.
+        // Canonicalization needs the path to exist, which a delete event's
+        // path no longer does -- fall back to the raw path there, since
+        // `notify` already reports it absolute in that case.
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        let relative = match canonical.strip_prefix(root) {
+            Ok(relative) => relative,
+            Err(_) => continue,
+        };
+
+        pending.insert(
+            Reload::Changed {
+                source_id: source_id.to_string(),
+                path: relative.to_string_lossy().replace('\\', "/"),
+            },
+            (),
+        );
+    }
+}
+
+/// Reimports every cached handle whose source/path matches `reload`, or
+/// whose dependency list names that same source/path pair (so a changed
+/// child propagates to its parents, even a parent that was itself loaded
+/// from a different source than the one that changed). Paths with no live
+/// handle are skipped.
+fn reload_asset(reload: Reload, handles: &Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>>) {
+    let Reload::Changed { source_id, path } = reload;
+
+    let reimports: Vec<_> = handles
+        .lock()
+        .expect("Programmer error: Thread panicked while holding handles lock")
+        .values()
+        .filter(|entry| {
+            (entry.source == source_id && entry.path == path)
+                || entry
+                    .dependencies
+                    .iter()
+                    .any(|(dep_source, dep_path)| *dep_source == source_id && *dep_path == path)
+        })
+        .map(|entry| entry.reimport.clone())
+        .collect();
+
+    if reimports.is_empty() {
+        debug!(
+            "Source {:?}: {:?} changed but no live handle depends on it",
+            source_id, path
+        );
+        return;
+    }
+
+    for reimport in reimports {
+        reimport();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn split_source_prefix_reads_the_scheme() {
+        assert_eq!(
+            split_source_prefix("tex://foo/bar.png"),
+            ("tex", "foo/bar.png")
+        );
+    }
+
+    #[test]
+    fn split_source_prefix_defaults_to_the_empty_source() {
+        assert_eq!(split_source_prefix("foo/bar.png"), ("", "foo/bar.png"));
+    }
+
+    #[test]
+    fn split_source_prefix_ignores_a_bare_colon() {
+        // A Windows-style path has a colon but no `://`, so it shouldn't be
+        // mistaken for a scheme.
+        assert_eq!(
+            split_source_prefix("C:\\foo\\bar.png"),
+            ("", "C:\\foo\\bar.png")
+        );
+    }
+
+    #[test]
+    fn collect_reloads_coalesces_duplicate_paths() {
+        let root = Path::new("/assets");
+        let mut pending = FnvHashMap::default();
+
+        let event = notify::Event::new(notify::EventKind::Any)
+            .add_path(PathBuf::from("/assets/a.png"))
+            .add_path(PathBuf::from("/assets/a.png"));
+        collect_reloads("default", root, Ok(event), &mut pending);
+
+        assert_eq!(pending.len(), 1);
+        assert!(pending.contains_key(&Reload::Changed {
+            source_id: "default".to_string(),
+            path: "a.png".to_string(),
+        }));
+    }
+
+    #[test]
+    fn collect_reloads_skips_paths_outside_root() {
+        let root = Path::new("/assets");
+        let mut pending = FnvHashMap::default();
+
+        let event = notify::Event::new(notify::EventKind::Any)
+            .add_path(PathBuf::from("/elsewhere/a.png"));
+        collect_reloads("default", root, Ok(event), &mut pending);
+
+        assert!(pending.is_empty());
+    }
+
+    fn counting_entry(path: &str, dependencies: Vec<(String, String)>) -> (HandleEntry, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        let entry = HandleEntry {
+            handle: Box::new(()),
+            source: String::new(),
+            path: path.to_string(),
+            dependencies,
+            reimport: Arc::new(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        };
+        (entry, count)
+    }
+
+    #[test]
+    fn reload_asset_cascades_a_changed_dependency_to_its_parent() {
+        let handles: Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>> = Default::default();
+        let (entry, reimported) =
+            counting_entry("material.ron", vec![(String::new(), "texture.png".to_string())]);
+        handles.lock().unwrap().insert(LoadInfoHash(1), entry);
+
+        reload_asset(
+            Reload::Changed {
+                source_id: String::new(),
+                path: "texture.png".to_string(),
+            },
+            &handles,
+        );
+
+        assert_eq!(reimported.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn reload_asset_ignores_an_unrelated_path() {
+        let handles: Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>> = Default::default();
+        let (entry, reimported) = counting_entry("material.ron", Vec::new());
+        handles.lock().unwrap().insert(LoadInfoHash(1), entry);
+
+        reload_asset(
+            Reload::Changed {
+                source_id: String::new(),
+                path: "unrelated.png".to_string(),
+            },
+            &handles,
+        );
+
+        assert_eq!(reimported.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn reload_asset_does_not_cascade_across_sources() {
+        let handles: Arc<Mutex<FnvHashMap<LoadInfoHash, HandleEntry>>> = Default::default();
+        let (entry, reimported) = counting_entry(
+            "material.ron",
+            vec![("other".to_string(), "texture.png".to_string())],
+        );
+        handles.lock().unwrap().insert(LoadInfoHash(1), entry);
+
+        // Same path, but the default ("") source, not "other" -- shouldn't cascade.
+        reload_asset(
+            Reload::Changed {
+                source_id: String::new(),
+                path: "texture.png".to_string(),
+            },
+            &handles,
+        );
+
+        assert_eq!(reimported.load(Ordering::SeqCst), 0);
     }
 }
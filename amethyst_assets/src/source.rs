@@ -0,0 +1,68 @@
+use std::path::PathBuf;
+
+use amethyst_error::Error;
+
+/// A place assets can be loaded from: a directory on the local filesystem,
+/// an in-memory store, a network endpoint, or anything else that can hand
+/// back bytes for a path.
+pub trait Source: Send + Sync + 'static {
+    /// Returns the last-modified time of the asset at `path`, used by
+    /// formats to decide whether cached data is stale.
+    fn modified(&self, path: &str) -> Result<u64, Error>;
+
+    /// Reads the raw bytes of the asset at `path`.
+    fn load(&self, path: &str) -> Result<Vec<u8>, Error>;
+
+    /// The root filesystem path this source is backed by, if it has one.
+    /// Defaults to `None` so sources that aren't rooted in the filesystem
+    /// (in-memory, network, ...) don't need to do anything to opt out;
+    /// `Loader::add_source` uses this to decide whether there's anything
+    /// for the hot-reload watcher to watch.
+    fn path(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// A `Source` backed by a directory on the local filesystem.
+pub struct Directory {
+    loc: PathBuf,
+}
+
+impl Directory {
+    /// Creates a new `Directory` source rooted at `loc`.
+    pub fn new<P: Into<PathBuf>>(loc: P) -> Self {
+        Directory { loc: loc.into() }
+    }
+
+    fn path_for(&self, path: &str) -> PathBuf {
+        self.loc.join(path)
+    }
+}
+
+impl Source for Directory {
+    fn modified(&self, path: &str) -> Result<u64, Error> {
+        use std::time::UNIX_EPOCH;
+
+        let path = self.path_for(path);
+        let metadata = std::fs::metadata(&path)
+            .map_err(|err| Error::from_string(format!("Failed to get metadata of {:?}: {}", path, err)))?;
+        let modified = metadata
+            .modified()
+            .map_err(|err| Error::from_string(format!("Failed to get mtime of {:?}: {}", path, err)))?;
+
+        Ok(modified
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0))
+    }
+
+    fn load(&self, path: &str) -> Result<Vec<u8>, Error> {
+        let path = self.path_for(path);
+        std::fs::read(&path)
+            .map_err(|err| Error::from_string(format!("Failed to read {:?}: {}", path, err)))
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        Some(self.loc.clone())
+    }
+}